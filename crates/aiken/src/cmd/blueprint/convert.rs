@@ -5,6 +5,8 @@ use aiken_project::{
 };
 use clap::ValueEnum;
 use miette::IntoDiagnostic;
+use pallas_codec::Fragment;
+use pallas_primitives::conway::PlutusData;
 use serde_json::json;
 use std::{env, fs::File, io::BufReader, path::PathBuf, process};
 
@@ -22,6 +24,11 @@ pub struct Args {
     #[clap(short, long)]
     validator: Option<String>,
 
+    /// A parameter to apply to the validator before conversion, as CBOR-hex or JSON
+    /// Plutus data. May be given multiple times; parameters are applied in order.
+    #[clap(short, long)]
+    parameter: Vec<String>,
+
     // Format to convert to
     #[clap(long, default_value = "cardano-cli")]
     to: Format,
@@ -30,6 +37,8 @@ pub struct Args {
 #[derive(Copy, Clone, ValueEnum)]
 pub enum Format {
     CardanoCli,
+    PlutusScript,
+    Json,
 }
 
 pub fn exec(
@@ -37,6 +46,7 @@ pub fn exec(
         directory,
         module,
         validator,
+        parameter,
         to,
     }: Args,
 ) -> miette::Result<()> {
@@ -75,29 +85,50 @@ pub fn exec(
         .unwrap_or_default()
         .cardano_cli_type();
 
+    let parameters = parameter
+        .iter()
+        .map(|raw| parse_parameter(raw))
+        .collect::<miette::Result<Vec<PlutusData>>>()?;
+
     // Perform the conversion
     let when_too_many =
         |known_validators| ProjectError::MoreThanOneValidatorFound { known_validators };
     let when_missing = |known_validators| ProjectError::NoValidatorNotFound { known_validators };
 
     let result =
-        blueprint.with_validator(title, when_too_many, when_missing, |validator| match to {
-            Format::CardanoCli => {
-                let cbor_bytes = validator.program.to_cbor().unwrap();
-
-                let mut double_cbor_bytes = Vec::new();
-
-                let mut cbor_encoder = pallas_codec::minicbor::Encoder::new(&mut double_cbor_bytes);
-
-                cbor_encoder.bytes(&cbor_bytes).unwrap();
-
-                let cbor_hex = hex::encode(double_cbor_bytes);
-
-                Ok(json!({
+        blueprint.with_validator(title, when_too_many, when_missing, |validator| {
+            let validator = parameters
+                .iter()
+                .try_fold(validator.clone(), |validator, param| {
+                    validator.apply(param.clone())
+                })
+                .into_diagnostic()?;
+
+            let cbor_bytes = validator.program.to_cbor().unwrap();
+
+            match to {
+                Format::CardanoCli => Ok(json!({
                     "type": cardano_cli_type,
                     "description": "Generated by Aiken",
-                    "cborHex": cbor_hex
-                }))
+                    "cborHex": double_cbor_hex(&cbor_bytes)
+                })),
+                Format::PlutusScript => Ok(json!({
+                    "type": format!("PlutusScript{}", validator.plutus_version),
+                    "description": "Generated by Aiken",
+                    "cborHex": double_cbor_hex(&cbor_bytes)
+                })),
+                // `validator` here is the post-`apply` binding, so `hash` is only
+                // correct for the parameterized script as long as `Validator::apply`
+                // recomputes it alongside `program`; it must not be read off the
+                // pre-`apply` validator.
+                Format::Json => Ok(json!({
+                    "hash": validator.hash.to_string(),
+                    "compiledCode": hex::encode(&cbor_bytes),
+                    "parameters": parameters
+                        .iter()
+                        .map(|p| hex::encode(p.encode_fragment().unwrap()))
+                        .collect::<Vec<_>>(),
+                })),
             }
         });
 
@@ -116,3 +147,71 @@ pub fn exec(
         }
     }
 }
+
+/// Wraps already-CBOR-encoded program bytes in an outer CBOR byte-string, matching
+/// the double-CBOR encoding `cardano-cli` and the node's text envelopes expect.
+fn double_cbor_hex(cbor_bytes: &[u8]) -> String {
+    let mut double_cbor_bytes = Vec::new();
+
+    let mut cbor_encoder = pallas_codec::minicbor::Encoder::new(&mut double_cbor_bytes);
+
+    cbor_encoder.bytes(cbor_bytes).unwrap();
+
+    hex::encode(double_cbor_bytes)
+}
+
+/// Parses a `--parameter` argument as CBOR-hex, falling back to the conventional
+/// detailed Plutus-JSON schema (`{"int": ...}`, `{"bytes": ...}`,
+/// `{"constructor": ..., "fields": [...]}`, ...) aiken uses everywhere else a
+/// user supplies Plutus data by hand.
+fn parse_parameter(raw: &str) -> miette::Result<PlutusData> {
+    if let Ok(bytes) = hex::decode(raw) {
+        if let Ok(data) = PlutusData::decode_fragment(&bytes) {
+            return Ok(data);
+        }
+    }
+
+    let json: serde_json::Value = serde_json::from_str(raw)
+        .map_err(|_| miette::miette!("invalid CBOR-hex or JSON Plutus data: {raw}"))?;
+
+    uplc::plutus_data::from_json(&json).into_diagnostic()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_cbor_hex_wraps_the_inner_cbor_in_a_byte_string() {
+        let cbor_bytes = vec![0x01, 0x02, 0x03];
+
+        // a 3-byte bytestring header (0x43) followed by the bytes themselves
+        assert_eq!(double_cbor_hex(&cbor_bytes), "43010203");
+    }
+
+    #[test]
+    fn parse_parameter_accepts_cbor_hex() {
+        // the integer 42 as Plutus data CBOR
+        let parsed = parse_parameter("182a").unwrap();
+
+        assert_eq!(
+            parsed,
+            PlutusData::BigInt(pallas_primitives::conway::BigInt::Int(42.into()))
+        );
+    }
+
+    #[test]
+    fn parse_parameter_accepts_detailed_plutus_json() {
+        let parsed = parse_parameter(r#"{"int": 42}"#).unwrap();
+
+        assert_eq!(
+            parsed,
+            PlutusData::BigInt(pallas_primitives::conway::BigInt::Int(42.into()))
+        );
+    }
+
+    #[test]
+    fn parse_parameter_rejects_garbage() {
+        assert!(parse_parameter("not cbor or json").is_err());
+    }
+}
@@ -0,0 +1,75 @@
+pub mod cost_model;
+pub mod eval_result;
+pub mod runtime;
+
+use cost_model::{CostModel, ExBudget};
+use runtime::BuiltinCall;
+use thiserror::Error as ThisError;
+
+use crate::ast::{DefaultFunction, NamedDeBruijn, Term, Value};
+
+pub use eval_result::EvalResult;
+
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("the validator ran out of execution units")]
+    OutOfExBudget,
+
+    #[error("{0}")]
+    Machine(String),
+}
+
+/// The CEK machine driving evaluation of a `Program`. Only the state relevant to
+/// budget/builtin-cost tracking is shown here; the `compute`/`return`/`apply`
+/// transition functions that drive the rest of evaluation live alongside this.
+pub struct Machine {
+    costs: CostModel,
+    ex_budget: ExBudget,
+    logs: Vec<String>,
+    builtin_calls: Vec<BuiltinCall>,
+}
+
+impl Machine {
+    pub fn new(costs: CostModel, initial_budget: ExBudget) -> Machine {
+        Machine {
+            costs,
+            ex_budget: initial_budget,
+            logs: Vec::new(),
+            builtin_calls: Vec::new(),
+        }
+    }
+
+    /// Dispatches a fully-saturated builtin application, charging its cost and
+    /// recording the `ExBudget` it actually consumed by diffing the machine's
+    /// budget immediately before and after the dispatch.
+    fn eval_builtin_app(
+        &mut self,
+        fun: DefaultFunction,
+        args: &[Value],
+    ) -> Result<Term<NamedDeBruijn>, Error> {
+        let before = self.ex_budget;
+
+        let cost = self.costs.builtin_cost(fun, args);
+
+        self.spend_budget(cost)?;
+
+        let result = fun
+            .call(args, &mut self.logs)
+            .map_err(|e| Error::Machine(e.to_string()))?;
+
+        self.builtin_calls
+            .push(BuiltinCall::new(fun, before - self.ex_budget));
+
+        Ok(result)
+    }
+
+    fn spend_budget(&mut self, cost: ExBudget) -> Result<(), Error> {
+        self.ex_budget = self.ex_budget - cost;
+
+        if self.ex_budget.mem < 0 || self.ex_budget.cpu < 0 {
+            return Err(Error::OutOfExBudget);
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use super::{cost_model::ExBudget, runtime::BuiltinCall, Error};
-use crate::ast::{Constant, NamedDeBruijn, Term};
+use crate::ast::{Constant, DefaultFunction, NamedDeBruijn, Term};
+use std::collections::HashMap;
+use std::fmt::Write as _;
 
 #[derive(Debug)]
 pub struct EvalResult {
@@ -65,4 +67,84 @@ impl EvalResult {
     pub fn result(&self) -> Result<Term<NamedDeBruijn>, Error> {
         self.result.clone()
     }
+
+    /// Aggregates the execution units spent on each builtin across the whole run,
+    /// e.g. to see whether a script's cost is dominated by hashing or by list ops.
+    pub fn cost_breakdown(&self) -> HashMap<DefaultFunction, ExBudget> {
+        let zero = ExBudget { mem: 0, cpu: 0 };
+
+        let mut breakdown: HashMap<DefaultFunction, ExBudget> = HashMap::new();
+
+        for call in &self.builtin_calls {
+            let entry = breakdown.entry(call.fun).or_insert(zero);
+
+            *entry = *entry + call.ex_budget;
+        }
+
+        breakdown
+    }
+
+    /// Emits collapsed folded-stack text (`stack_frame;builtin count` per line,
+    /// cpu units) suitable for piping into standard flamegraph tooling (e.g.
+    /// `inferno-flamegraph`), so a script's cost can be visualized per builtin.
+    pub fn to_flamegraph(&self) -> String {
+        self.folded_stacks(|budget| budget.cpu)
+    }
+
+    /// Same as [`EvalResult::to_flamegraph`], folded on memory units instead.
+    pub fn to_flamegraph_mem(&self) -> String {
+        self.folded_stacks(|budget| budget.mem)
+    }
+
+    fn folded_stacks(&self, units: impl Fn(&ExBudget) -> i64) -> String {
+        let mut folded = String::new();
+
+        for call in &self.builtin_calls {
+            let _ = writeln!(folded, "eval;{:?} {}", call.fun, units(&call.ex_budget));
+        }
+
+        folded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval_result(builtin_calls: Vec<BuiltinCall>) -> EvalResult {
+        EvalResult::new(
+            Ok(Term::Constant(Constant::Unit.into())),
+            ExBudget { mem: 0, cpu: 0 },
+            ExBudget { mem: 0, cpu: 0 },
+            vec![],
+            builtin_calls,
+        )
+    }
+
+    #[test]
+    fn cost_breakdown_of_no_calls_is_empty() {
+        let result = eval_result(vec![]);
+
+        assert!(result.cost_breakdown().is_empty());
+    }
+
+    #[test]
+    fn cost_breakdown_sums_exactly_without_a_phantom_budget() {
+        let result = eval_result(vec![
+            BuiltinCall::new(DefaultFunction::AddInteger, ExBudget { mem: 1, cpu: 10 }),
+            BuiltinCall::new(DefaultFunction::AddInteger, ExBudget { mem: 2, cpu: 20 }),
+            BuiltinCall::new(DefaultFunction::EqualsInteger, ExBudget { mem: 3, cpu: 30 }),
+        ]);
+
+        let breakdown = result.cost_breakdown();
+
+        assert_eq!(
+            breakdown.get(&DefaultFunction::AddInteger),
+            Some(&ExBudget { mem: 3, cpu: 30 })
+        );
+        assert_eq!(
+            breakdown.get(&DefaultFunction::EqualsInteger),
+            Some(&ExBudget { mem: 3, cpu: 30 })
+        );
+    }
 }
@@ -0,0 +1,17 @@
+use super::cost_model::ExBudget;
+use crate::ast::DefaultFunction;
+
+/// A single builtin dispatch observed while a script ran, and the execution units
+/// it consumed — captured by diffing the machine's budget immediately before and
+/// after the dispatch.
+#[derive(Debug, Clone)]
+pub struct BuiltinCall {
+    pub fun: DefaultFunction,
+    pub ex_budget: ExBudget,
+}
+
+impl BuiltinCall {
+    pub fn new(fun: DefaultFunction, ex_budget: ExBudget) -> Self {
+        BuiltinCall { fun, ex_budget }
+    }
+}
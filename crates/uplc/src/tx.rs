@@ -0,0 +1,14 @@
+pub mod error;
+pub mod eval;
+pub mod phase_one;
+pub mod resolver;
+pub mod script_context;
+pub mod to_plutus_data;
+
+pub use error::Error;
+pub use eval::{eval_tx, TxEvaluation};
+pub use phase_one::eval_phase_one;
+#[cfg(feature = "blockfrost")]
+pub use resolver::BlockfrostProvider;
+pub use resolver::{eval_phase_one_resolved, UtxoProvider};
+pub use script_context::{DataLookupTable, ResolvedInput, ScriptPurpose, ScriptVersion};
@@ -0,0 +1,33 @@
+use pallas_addresses::Error as AddressError;
+use pallas_primitives::conway::{RedeemersKey, TransactionInput};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Could not resolve a script input: {0:?}")]
+    ResolvedInputNotFound(TransactionInput),
+
+    #[error("Invalid address")]
+    Address(#[from] AddressError),
+
+    #[error("Withdrawal address is not a stake address")]
+    BadWithdrawalAddress,
+
+    #[error("Required redeemers mismatch: missing {missing:?}, extra {extra:?}")]
+    RequiredRedeemersMismatch {
+        missing: Vec<String>,
+        extra: Vec<String>,
+    },
+
+    #[error("Could not build redeemer key for script purpose")]
+    MissingRedeemerKey,
+
+    #[error("No such redeemer: {0:?}")]
+    MissingRedeemer(RedeemersKey),
+
+    #[error("UTxO provider request failed: {0}")]
+    Provider(String),
+
+    #[error("Failed to decode CBOR returned by the UTxO provider: {0}")]
+    Decode(String),
+}
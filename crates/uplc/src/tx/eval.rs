@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use pallas_primitives::conway::{MintedTx, RedeemersKey};
+
+use crate::{
+    ast::{NamedDeBruijn, Program},
+    machine::cost_model::ExBudget,
+};
+
+use super::{
+    error::Error,
+    phase_one::{build_redeemer_key, scripts_needed},
+    script_context::{DataLookupTable, ResolvedInput, ScriptVersion},
+    to_plutus_data::ScriptContext,
+};
+
+/// Execution units a redeemer actually consumed, and any logs its script emitted.
+#[derive(Debug, Clone)]
+pub struct RedeemerExecutionUnits {
+    pub budget: ExBudget,
+    pub logs: Vec<String>,
+}
+
+/// A redeemer whose script failed: evaluation raised an error (including running
+/// out of budget), or the script returned something other than `True` or unit.
+#[derive(Debug, Clone)]
+pub struct FailedRedeemer {
+    pub error: String,
+    pub logs: Vec<String>,
+}
+
+/// Outcome of running every redeemer `tx` needs to completion.
+#[derive(Debug, Default)]
+pub struct TxEvaluation {
+    pub redeemers: HashMap<RedeemersKey, RedeemerExecutionUnits>,
+    pub failed: HashMap<RedeemersKey, FailedRedeemer>,
+}
+
+impl TxEvaluation {
+    /// Summed execution units across every redeemer that evaluated successfully.
+    pub fn total(&self) -> ExBudget {
+        let zero = ExBudget { mem: 0, cpu: 0 };
+
+        self.redeemers
+            .values()
+            .fold(zero, |acc, r| acc + r.budget)
+    }
+}
+
+/// A generous starting budget, equal to mainnet's whole per-transaction limit,
+/// so a redeemer's reported cost is bounded only by what it actually does, up to
+/// what a single transaction may spend.
+const STARTING_BUDGET: ExBudget = ExBudget {
+    mem: 14_000_000,
+    cpu: 10_000_000_000,
+};
+
+/// Runs every redeemer `tx` is expected to supply to completion and reports the
+/// execution units each one actually consumed, mirroring a `forge script` dry run
+/// so a wallet can fill in minimal `ExUnits` instead of guessing. Scripts that fail
+/// are reported separately, with their logs attached, rather than aborting the
+/// whole estimation.
+pub fn eval_tx(
+    tx: &MintedTx,
+    utxos: &[ResolvedInput],
+    lookup_table: &DataLookupTable,
+) -> Result<TxEvaluation, Error> {
+    let needed = scripts_needed(tx, utxos)?;
+
+    let scripts = lookup_table.scripts();
+
+    let redeemers = tx
+        .transaction_witness_set
+        .redeemer
+        .as_deref()
+        .map(|m| m.iter().cloned().collect::<HashMap<_, _>>())
+        .unwrap_or_default();
+
+    let mut evaluation = TxEvaluation::default();
+
+    for (purpose, script_hash) in &needed {
+        let Some(key) = build_redeemer_key(tx, purpose)? else {
+            continue;
+        };
+
+        let (Some(script), Some(redeemer)) = (scripts.get(script_hash), redeemers.get(&key))
+        else {
+            continue;
+        };
+
+        let script_context = ScriptContext::build(tx, purpose, utxos)?;
+
+        // V1/V2 validators take their datum (when spending), redeemer, and the
+        // script context as separate applied arguments; V3 validators take a
+        // single `ScriptContext` argument with the redeemer embedded in it.
+        let program = match script {
+            ScriptVersion::Native(_) => continue,
+            ScriptVersion::V1(bytes) => script_context
+                .args_v1_v2(redeemer.data.clone())
+                .into_iter()
+                .fold(
+                    Program::<NamedDeBruijn>::try_from(bytes.as_ref())
+                        .map_err(|e| Error::Decode(format!("{e:?}")))?,
+                    |program, arg| program.apply_data(arg),
+                ),
+            ScriptVersion::V2(bytes) => script_context
+                .args_v1_v2(redeemer.data.clone())
+                .into_iter()
+                .fold(
+                    Program::<NamedDeBruijn>::try_from(bytes.as_ref())
+                        .map_err(|e| Error::Decode(format!("{e:?}")))?,
+                    |program, arg| program.apply_data(arg),
+                ),
+            ScriptVersion::V3(bytes) => Program::<NamedDeBruijn>::try_from(bytes.as_ref())
+                .map_err(|e| Error::Decode(format!("{e:?}")))?
+                .apply_data(script_context.arg_v3(redeemer.data.clone())),
+        };
+
+        let mut result = program.eval(STARTING_BUDGET);
+
+        // A script that runs to completion but returns `Term::Error` (the normal
+        // shape of a Plutus validator calling `error`) or anything other than
+        // `True`/unit is a failed redeemer, not a successful one, even though
+        // `result()` itself is `Ok`.
+        if result.failed(false) {
+            let error = match result.result() {
+                Err(err) => err.to_string(),
+                Ok(term) => format!("validator failed: {term:?}"),
+            };
+
+            evaluation.failed.insert(
+                key,
+                FailedRedeemer {
+                    error,
+                    logs: result.logs(),
+                },
+            );
+        } else {
+            evaluation.redeemers.insert(
+                key,
+                RedeemerExecutionUnits {
+                    budget: result.cost(),
+                    logs: result.logs(),
+                },
+            );
+        }
+    }
+
+    Ok(evaluation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pallas_primitives::conway::RedeemerTag;
+
+    fn redeemer_key(index: u32) -> RedeemersKey {
+        RedeemersKey {
+            tag: RedeemerTag::Spend,
+            index,
+        }
+    }
+
+    #[test]
+    fn total_of_no_redeemers_is_zero() {
+        let evaluation = TxEvaluation::default();
+
+        assert_eq!(evaluation.total(), ExBudget { mem: 0, cpu: 0 });
+    }
+
+    #[test]
+    fn total_sums_exactly_without_a_starting_budget() {
+        let mut evaluation = TxEvaluation::default();
+
+        evaluation.redeemers.insert(
+            redeemer_key(0),
+            RedeemerExecutionUnits {
+                budget: ExBudget {
+                    mem: 100,
+                    cpu: 200,
+                },
+                logs: vec![],
+            },
+        );
+
+        evaluation.redeemers.insert(
+            redeemer_key(1),
+            RedeemerExecutionUnits {
+                budget: ExBudget { mem: 1, cpu: 1 },
+                logs: vec![],
+            },
+        );
+
+        assert_eq!(
+            evaluation.total(),
+            ExBudget {
+                mem: 101,
+                cpu: 201
+            }
+        );
+    }
+}
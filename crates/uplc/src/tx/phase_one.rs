@@ -5,8 +5,8 @@ use super::{
 use itertools::Itertools;
 use pallas_addresses::{Address, ScriptHash, ShelleyPaymentPart, StakePayload};
 use pallas_primitives::conway::{
-    Certificate, MintedTx, PolicyId, RedeemerTag, RedeemersKey, RewardAccount, StakeCredential,
-    TransactionOutput,
+    Certificate, GovAction, MintedTx, PolicyId, RedeemerTag, RedeemersKey, RewardAccount,
+    StakeCredential, TransactionOutput, Voter,
 };
 use std::collections::HashMap;
 
@@ -138,18 +138,59 @@ pub fn scripts_needed(tx: &MintedTx, utxos: &[ResolvedInput]) -> Result<ScriptsN
         })
         .unwrap_or_default();
 
-    // TODO
-    assert!(txb.proposal_procedures.is_none());
-    assert!(txb.voting_procedures.is_none());
+    let mut vote = txb
+        .voting_procedures
+        .as_deref()
+        .map(|m| {
+            m.iter()
+                .filter_map(|(voter, _)| match voter {
+                    Voter::ConstitutionalCommitteeHotScriptHash(h) | Voter::DRepScriptHash(h) => {
+                        Some((ScriptPurpose::Voting(voter.clone()), *h))
+                    }
+                    _ => None,
+                })
+                .collect::<ScriptsNeeded>()
+        })
+        .unwrap_or_default();
+
+    let mut propose = txb
+        .proposal_procedures
+        .as_deref()
+        .map(|m| {
+            m.iter()
+                .enumerate()
+                .filter_map(|(index, proposal)| {
+                    guardrail_script(&proposal.gov_action)
+                        .map(|h| (ScriptPurpose::Proposing(index as u32, proposal.clone()), h))
+                })
+                .collect::<ScriptsNeeded>()
+        })
+        .unwrap_or_default();
 
     needed.append(&mut spend);
     needed.append(&mut reward);
     needed.append(&mut cert);
     needed.append(&mut mint);
+    needed.append(&mut vote);
+    needed.append(&mut propose);
 
     Ok(needed)
 }
 
+/// the script hash (if any) that gates a governance action, e.g. the constitution's
+/// or a protocol-parameter-update's guardrail script
+fn guardrail_script(gov_action: &GovAction) -> Option<ScriptHash> {
+    match gov_action {
+        GovAction::ParameterChange(_, _, script_hash) => *script_hash,
+        GovAction::TreasuryWithdrawals(_, script_hash) => *script_hash,
+        GovAction::NewConstitution(_, constitution) => constitution.guardrail_script,
+        GovAction::HardForkInitiation(..)
+        | GovAction::NoConfidence(..)
+        | GovAction::UpdateCommittee(..)
+        | GovAction::Information => None,
+    }
+}
+
 /// hasExactSetOfRedeemers in Ledger Spec, but we pass `txscripts` directly
 pub fn has_exact_set_of_redeemers(
     tx: &MintedTx,
@@ -215,7 +256,7 @@ pub fn has_exact_set_of_redeemers(
 /// builds a redeemer pointer (tag, index) from a script purpose by setting the tag
 /// according to the type of the script purpose, and the index according to the
 /// placement of script purpose inside its container.
-fn build_redeemer_key(
+pub(crate) fn build_redeemer_key(
     tx: &MintedTx,
     script_purpose: &ScriptPurpose,
 ) -> Result<Option<RedeemersKey>, Error> {
@@ -305,5 +346,98 @@ fn build_redeemer_key(
 
             Ok(redeemer_key)
         }
+
+        ScriptPurpose::Voting(voter) => {
+            let voters: Vec<&Voter> = tx_body
+                .voting_procedures
+                .as_deref()
+                .map(|m| m.iter().map(|(v, _)| v).collect())
+                .unwrap_or_default();
+
+            let redeemer_key = vote_redeemer_index(&voters, voter).map(|index| RedeemersKey {
+                tag: RedeemerTag::Vote,
+                index: index as u32,
+            });
+
+            Ok(redeemer_key)
+        }
+
+        ScriptPurpose::Proposing(index, _) => Ok(Some(RedeemersKey {
+            tag: RedeemerTag::Propose,
+            index: *index,
+        })),
+    }
+}
+
+/// the position `voter` would occupy among `voters` once sorted, matching the
+/// order the Ledger assigns `RedeemerTag::Vote` indices in
+fn vote_redeemer_index(voters: &[&Voter], voter: &Voter) -> Option<usize> {
+    let mut sorted = voters.to_vec();
+
+    sorted.sort();
+
+    sorted.iter().position(|x| *x == voter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pallas_addresses::ScriptHash;
+    use pallas_primitives::conway::Constitution;
+
+    fn script_hash(byte: u8) -> ScriptHash {
+        ScriptHash::from([byte; 28])
+    }
+
+    #[test]
+    fn vote_redeemer_index_follows_sorted_voter_order() {
+        let drep_a = Voter::DRepScriptHash(script_hash(0x01));
+        let drep_b = Voter::DRepScriptHash(script_hash(0x02));
+        let committee = Voter::ConstitutionalCommitteeHotScriptHash(script_hash(0x00));
+
+        // unsorted on purpose: the function must sort before indexing
+        let voters = vec![&drep_b, &committee, &drep_a];
+
+        assert_eq!(vote_redeemer_index(&voters, &committee), Some(0));
+        assert_eq!(vote_redeemer_index(&voters, &drep_a), Some(1));
+        assert_eq!(vote_redeemer_index(&voters, &drep_b), Some(2));
+    }
+
+    #[test]
+    fn vote_redeemer_index_missing_voter_is_none() {
+        let drep_a = Voter::DRepScriptHash(script_hash(0x01));
+        let stake_pool = Voter::StakePoolKeyHash(script_hash(0x02));
+
+        assert_eq!(vote_redeemer_index(&[&drep_a], &stake_pool), None);
+    }
+
+    #[test]
+    fn guardrail_script_reads_parameter_change_script() {
+        let hash = Some(script_hash(0x09));
+
+        let gov_action = GovAction::ParameterChange(None, Box::default(), hash);
+
+        assert_eq!(guardrail_script(&gov_action), hash);
+    }
+
+    #[test]
+    fn guardrail_script_reads_constitution_guardrail() {
+        let hash = Some(script_hash(0x0a));
+
+        let gov_action = GovAction::NewConstitution(
+            None,
+            Constitution {
+                anchor: Default::default(),
+                guardrail_script: hash,
+            },
+        );
+
+        assert_eq!(guardrail_script(&gov_action), hash);
+    }
+
+    #[test]
+    fn guardrail_script_is_none_for_unscripted_actions() {
+        assert_eq!(guardrail_script(&GovAction::NoConfidence(None)), None);
+        assert_eq!(guardrail_script(&GovAction::Information), None);
     }
 }
@@ -0,0 +1,61 @@
+use super::{
+    error::Error,
+    phase_one::eval_phase_one,
+    script_context::{DataLookupTable, ResolvedInput},
+};
+use pallas_primitives::conway::{MintedTx, TransactionInput, TransactionOutput};
+
+#[cfg(feature = "blockfrost")]
+pub mod blockfrost;
+
+#[cfg(feature = "blockfrost")]
+pub use blockfrost::BlockfrostProvider;
+
+/// A pluggable source of on-chain data: given a `TransactionInput`, produces the
+/// `TransactionOutput` it's spending. Implement this against Blockfrost, a local
+/// node, or an in-memory map of known UTxOs.
+#[async_trait::async_trait]
+pub trait UtxoProvider {
+    async fn resolve(&self, input: &TransactionInput) -> Result<TransactionOutput, Error>;
+}
+
+/// Resolves every input and reference input of `tx` through `provider`, producing
+/// the `ResolvedInput` list `eval_phase_one` expects.
+pub async fn resolve_inputs(
+    tx: &MintedTx<'_>,
+    provider: &dyn UtxoProvider,
+) -> Result<Vec<ResolvedInput>, Error> {
+    let txb = &tx.transaction_body;
+
+    let mut inputs: Vec<&TransactionInput> = txb.inputs.iter().collect();
+
+    if let Some(reference_inputs) = &txb.reference_inputs {
+        inputs.extend(reference_inputs.iter());
+    }
+
+    let mut resolved = Vec::with_capacity(inputs.len());
+
+    for input in inputs {
+        let output = provider.resolve(input).await?;
+
+        resolved.push(ResolvedInput {
+            input: input.clone(),
+            output,
+        });
+    }
+
+    Ok(resolved)
+}
+
+/// Resolves every input of `tx` through `provider` and runs phase-one validation,
+/// so a transaction pulled off-chain can be validated without the caller having to
+/// assemble its UTxO set by hand.
+pub async fn eval_phase_one_resolved(
+    tx: &MintedTx<'_>,
+    provider: &dyn UtxoProvider,
+    lookup_table: &DataLookupTable,
+) -> Result<(), Error> {
+    let utxos = resolve_inputs(tx, provider).await?;
+
+    eval_phase_one(tx, &utxos, lookup_table)
+}
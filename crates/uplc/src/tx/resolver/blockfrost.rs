@@ -0,0 +1,67 @@
+use super::UtxoProvider;
+use crate::tx::error::Error;
+use pallas_codec::minicbor;
+use pallas_primitives::conway::{MintedTx, TransactionInput, TransactionOutput};
+
+/// A `UtxoProvider` backed by a Blockfrost-compatible HTTP API. Gated behind the
+/// `blockfrost` feature so the core `uplc` evaluation crate doesn't pull in an
+/// HTTP client and async runtime for callers who don't need it.
+pub struct BlockfrostProvider {
+    base_url: String,
+    project_id: String,
+    client: reqwest::Client,
+}
+
+impl BlockfrostProvider {
+    pub fn new(base_url: impl Into<String>, project_id: impl Into<String>) -> Self {
+        BlockfrostProvider {
+            base_url: base_url.into(),
+            project_id: project_id.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct TxCborResponse {
+    cbor: String,
+}
+
+#[async_trait::async_trait]
+impl UtxoProvider for BlockfrostProvider {
+    /// Fetches the producing transaction's CBOR from `/txs/{hash}/cbor` and picks
+    /// out the output at `input.index`.
+    ///
+    /// Note: this decodes the producing transaction as a Conway-era `MintedTx`.
+    /// Blockfrost returns the transaction in whatever era it was actually
+    /// submitted in, so resolving an input that was produced by a pre-Conway
+    /// (e.g. Babbage or earlier) transaction will fail to decode here.
+    async fn resolve(&self, input: &TransactionInput) -> Result<TransactionOutput, Error> {
+        let tx_hash = hex::encode(input.transaction_id);
+
+        let url = format!("{}/txs/{}/cbor", self.base_url, tx_hash);
+
+        let response: TxCborResponse = self
+            .client
+            .get(url)
+            .header("project_id", &self.project_id)
+            .send()
+            .await
+            .map_err(|e| Error::Provider(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| Error::Provider(e.to_string()))?;
+
+        let cbor_bytes = hex::decode(response.cbor).map_err(|e| Error::Decode(e.to_string()))?;
+
+        let producing_tx: MintedTx =
+            minicbor::decode(&cbor_bytes).map_err(|e| Error::Decode(e.to_string()))?;
+
+        producing_tx
+            .transaction_body
+            .outputs
+            .get(input.index as usize)
+            .map(|output| TransactionOutput::from(output.clone()))
+            .ok_or_else(|| Error::ResolvedInputNotFound(input.clone()))
+    }
+}
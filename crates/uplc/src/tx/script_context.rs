@@ -0,0 +1,49 @@
+use pallas_addresses::ScriptHash;
+use pallas_primitives::conway::{
+    Certificate, NativeScript, PlutusV1Script, PlutusV2Script, PlutusV3Script, PolicyId,
+    ProposalProcedure, StakeCredential, TransactionInput, TransactionOutput, Voter,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ResolvedInput {
+    pub input: TransactionInput,
+    pub output: TransactionOutput,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ScriptVersion {
+    Native(NativeScript),
+    V1(PlutusV1Script),
+    V2(PlutusV2Script),
+    V3(PlutusV3Script),
+}
+
+/// Reason a script is required to run, mirroring `ScriptPurpose` in the Ledger spec.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ScriptPurpose {
+    Minting(PolicyId),
+    Spending(TransactionInput),
+    Rewarding(StakeCredential),
+    Certifying(Certificate),
+    /// A script-backed voter casting a vote on a governance action.
+    Voting(Voter),
+    /// A proposal procedure whose governance action is guarded by a script, at its
+    /// positional index within `proposal_procedures`.
+    Proposing(u32, ProposalProcedure),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DataLookupTable {
+    scripts: HashMap<ScriptHash, ScriptVersion>,
+}
+
+impl DataLookupTable {
+    pub fn new(scripts: HashMap<ScriptHash, ScriptVersion>) -> Self {
+        DataLookupTable { scripts }
+    }
+
+    pub fn scripts(&self) -> HashMap<ScriptHash, ScriptVersion> {
+        self.scripts.clone()
+    }
+}
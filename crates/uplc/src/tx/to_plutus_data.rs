@@ -0,0 +1,429 @@
+use pallas_codec::utils::CborWrap;
+use pallas_crypto::hash::Hasher;
+use pallas_primitives::conway::{
+    BigInt, Constr, DatumHash, DatumOption, MaybeIndefArray, MintedTx, PlutusData, StakeCredential,
+    TransactionOutput, Value, Voter,
+};
+use std::collections::HashMap;
+
+use super::{
+    error::Error,
+    script_context::{ResolvedInput, ScriptPurpose},
+};
+
+/// A view of the transaction a script can inspect while it runs, mirroring the
+/// subset of fields the Plutus ledger API exposes as `TxInfo`.
+///
+/// Only inputs, outputs, fee, mint and required signatories are encoded, in that
+/// order; certificates, withdrawals, votes and proposal procedures are not part
+/// of it. Datums from the witness set are used only to resolve output datums.
+#[derive(Debug, Clone)]
+pub struct TxInfo {
+    inputs: Vec<ResolvedInput>,
+    outputs: Vec<TransactionOutput>,
+    fee: u64,
+    mint: Vec<(Vec<u8>, Vec<(Vec<u8>, i64)>)>,
+    required_signatories: Vec<Vec<u8>>,
+    datums: HashMap<DatumHash, PlutusData>,
+}
+
+impl TxInfo {
+    /// Builds the `TxInfo` a script run against `tx` would see, resolving its
+    /// inputs against `utxos`.
+    pub fn from_transaction(tx: &MintedTx, utxos: &[ResolvedInput]) -> Result<TxInfo, Error> {
+        let txb = &tx.transaction_body;
+
+        let inputs = txb
+            .inputs
+            .iter()
+            .map(|input| {
+                utxos
+                    .iter()
+                    .find(|utxo| utxo.input == *input)
+                    .cloned()
+                    .ok_or_else(|| Error::ResolvedInputNotFound(input.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let outputs = txb.outputs.iter().map(|output| output.clone().into()).collect();
+
+        let mint = txb
+            .mint
+            .as_deref()
+            .map(|mint| {
+                mint.iter()
+                    .map(|(policy_id, assets)| {
+                        let assets = assets
+                            .iter()
+                            .map(|(asset_name, amount)| (asset_name.to_vec(), i64::from(*amount)))
+                            .collect();
+
+                        (policy_id.to_vec(), assets)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let required_signatories = txb
+            .required_signers
+            .as_deref()
+            .map(|signers| signers.iter().map(|h| h.to_vec()).collect())
+            .unwrap_or_default();
+
+        let datums = tx
+            .transaction_witness_set
+            .plutus_data
+            .as_deref()
+            .map(|datums| {
+                datums
+                    .iter()
+                    .map(|datum| (Hasher::<256>::hash_cbor(datum), datum.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(TxInfo {
+            inputs,
+            outputs,
+            fee: txb.fee,
+            mint,
+            required_signatories,
+            datums,
+        })
+    }
+
+    /// The datum attached to `output`, resolving a datum hash against the
+    /// datums carried in the transaction's witness set.
+    fn datum_of(&self, output: &TransactionOutput) -> Option<PlutusData> {
+        match datum_option(output)? {
+            DatumOption::Data(CborWrap(data)) => Some(data),
+            DatumOption::Hash(hash) => self.datums.get(&hash).cloned(),
+        }
+    }
+
+    fn to_plutus_data(&self) -> PlutusData {
+        constr(
+            0,
+            vec![
+                PlutusData::Array(
+                    self.inputs
+                        .iter()
+                        .map(|utxo| tx_in_info(utxo, self.datum_of(&utxo.output)))
+                        .collect(),
+                ),
+                PlutusData::Array(
+                    self.outputs
+                        .iter()
+                        .map(|output| tx_out_data(output, self.datum_of(output)))
+                        .collect(),
+                ),
+                PlutusData::BigInt(BigInt::Int(self.fee.into())),
+                value_data(0, &self.mint),
+                PlutusData::Array(
+                    self.required_signatories
+                        .iter()
+                        .map(|h| PlutusData::BoundedBytes(h.clone().into()))
+                        .collect(),
+                ),
+            ],
+        )
+    }
+}
+
+/// The portion of a transaction visible to a running Plutus script: the reason
+/// it's running (its `ScriptPurpose`) plus the surrounding `TxInfo`, encoded the
+/// way the ledger's `ScriptContext` is passed to a script.
+#[derive(Debug, Clone)]
+pub struct ScriptContext {
+    purpose: ScriptPurpose,
+    tx_info: TxInfo,
+    datum: Option<PlutusData>,
+}
+
+impl ScriptContext {
+    /// Builds the script context a script run for `purpose` would see, looking
+    /// up the spent output's datum when `purpose` is `Spending`.
+    pub fn build(
+        tx: &MintedTx,
+        purpose: &ScriptPurpose,
+        utxos: &[ResolvedInput],
+    ) -> Result<ScriptContext, Error> {
+        let tx_info = TxInfo::from_transaction(tx, utxos)?;
+
+        let datum = match purpose {
+            ScriptPurpose::Spending(input) => utxos
+                .iter()
+                .find(|utxo| utxo.input == *input)
+                .and_then(|utxo| tx_info.datum_of(&utxo.output)),
+            _ => None,
+        };
+
+        Ok(ScriptContext {
+            purpose: purpose.clone(),
+            tx_info,
+            datum,
+        })
+    }
+
+    /// Arguments a Plutus V1/V2 validator expects applied in order: the
+    /// spent output's datum when `purpose` is `Spending`, then the redeemer,
+    /// then the script context.
+    pub fn args_v1_v2(&self, redeemer: PlutusData) -> Vec<PlutusData> {
+        let mut args = Vec::new();
+
+        if let Some(datum) = &self.datum {
+            args.push(datum.clone());
+        }
+
+        args.push(redeemer);
+        args.push(self.to_plutus_data());
+
+        args
+    }
+
+    /// The single `ScriptContext` argument a Plutus V3 validator expects, with
+    /// the redeemer embedded in it rather than applied separately.
+    pub fn arg_v3(&self, redeemer: PlutusData) -> PlutusData {
+        constr(
+            0,
+            vec![self.tx_info.to_plutus_data(), redeemer, self.purpose_data()],
+        )
+    }
+
+    fn to_plutus_data(&self) -> PlutusData {
+        constr(0, vec![self.tx_info.to_plutus_data(), self.purpose_data()])
+    }
+
+    fn purpose_data(&self) -> PlutusData {
+        match &self.purpose {
+            ScriptPurpose::Minting(policy_id) => {
+                constr(0, vec![PlutusData::BoundedBytes(policy_id.to_vec().into())])
+            }
+            ScriptPurpose::Spending(input) => constr(
+                1,
+                vec![constr(
+                    0,
+                    vec![
+                        PlutusData::BoundedBytes(input.transaction_id.to_vec().into()),
+                        PlutusData::BigInt(BigInt::Int(input.index.into())),
+                    ],
+                )],
+            ),
+            ScriptPurpose::Rewarding(credential) => {
+                constr(2, vec![stake_credential_data(credential)])
+            }
+            // Ledger certificates aren't encoded field-for-field here; a validator
+            // that inspects certificate contents needs this filled in further.
+            ScriptPurpose::Certifying(_) => constr(3, vec![constr(0, vec![])]),
+            ScriptPurpose::Voting(voter) => constr(4, vec![voter_data(voter)]),
+            ScriptPurpose::Proposing(index, _) => constr(
+                5,
+                vec![PlutusData::BigInt(BigInt::Int((*index as i64).into()))],
+            ),
+        }
+    }
+}
+
+fn datum_option(output: &TransactionOutput) -> Option<DatumOption> {
+    match output {
+        TransactionOutput::Legacy(output) => output.datum_hash.map(DatumOption::Hash),
+        TransactionOutput::PostAlonzo(output) => output.datum_option.clone(),
+    }
+}
+
+fn tx_in_info(utxo: &ResolvedInput, datum: Option<PlutusData>) -> PlutusData {
+    constr(
+        0,
+        vec![
+            constr(
+                0,
+                vec![
+                    PlutusData::BoundedBytes(utxo.input.transaction_id.to_vec().into()),
+                    PlutusData::BigInt(BigInt::Int(utxo.input.index.into())),
+                ],
+            ),
+            tx_out_data(&utxo.output, datum),
+        ],
+    )
+}
+
+fn tx_out_data(output: &TransactionOutput, datum: Option<PlutusData>) -> PlutusData {
+    let (address, value) = match output {
+        TransactionOutput::Legacy(output) => (output.address.to_vec(), output.amount.clone()),
+        TransactionOutput::PostAlonzo(output) => (output.address.to_vec(), output.value.clone()),
+    };
+
+    let (lovelace, assets) = match &value {
+        Value::Coin(coin) => (*coin, vec![]),
+        Value::Multiasset(coin, assets) => (
+            *coin,
+            assets
+                .iter()
+                .map(|(policy_id, assets)| {
+                    (
+                        policy_id.to_vec(),
+                        assets
+                            .iter()
+                            .map(|(asset_name, amount)| (asset_name.to_vec(), u64::from(*amount) as i64))
+                            .collect(),
+                    )
+                })
+                .collect(),
+        ),
+    };
+
+    let datum_data = match datum {
+        Some(datum) => constr(2, vec![datum]),
+        None => match datum_option(output) {
+            Some(DatumOption::Hash(hash)) => {
+                constr(1, vec![PlutusData::BoundedBytes(hash.to_vec().into())])
+            }
+            _ => constr(0, vec![]),
+        },
+    };
+
+    constr(
+        0,
+        vec![
+            PlutusData::BoundedBytes(address),
+            value_data(lovelace, &assets),
+            datum_data,
+        ],
+    )
+}
+
+/// Encodes a ledger `Value`/`Mint` bundle as the nested
+/// `Map currency_symbol (Map token_name quantity)` a Plutus script expects,
+/// with the ada entry keyed by the empty currency symbol and token name.
+fn value_data(lovelace: u64, assets: &[(Vec<u8>, Vec<(Vec<u8>, i64)>)]) -> PlutusData {
+    let ada = (
+        PlutusData::BoundedBytes(Vec::new().into()),
+        PlutusData::Map(
+            vec![(
+                PlutusData::BoundedBytes(Vec::new().into()),
+                PlutusData::BigInt(BigInt::Int(lovelace.into())),
+            )]
+            .into(),
+        ),
+    );
+
+    let rest = assets.iter().map(|(policy_id, tokens)| {
+        (
+            PlutusData::BoundedBytes(policy_id.clone().into()),
+            PlutusData::Map(
+                tokens
+                    .iter()
+                    .map(|(asset_name, amount)| {
+                        (
+                            PlutusData::BoundedBytes(asset_name.clone().into()),
+                            PlutusData::BigInt(BigInt::Int((*amount).into())),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+        )
+    });
+
+    PlutusData::Map(std::iter::once(ada).chain(rest).collect::<Vec<_>>().into())
+}
+
+fn stake_credential_data(credential: &StakeCredential) -> PlutusData {
+    match credential {
+        StakeCredential::AddrKeyhash(h) => {
+            constr(0, vec![PlutusData::BoundedBytes(h.to_vec().into())])
+        }
+        StakeCredential::Scripthash(h) => {
+            constr(1, vec![PlutusData::BoundedBytes(h.to_vec().into())])
+        }
+    }
+}
+
+fn voter_data(voter: &Voter) -> PlutusData {
+    match voter {
+        Voter::ConstitutionalCommitteeHotKeyHash(h) => constr(
+            0,
+            vec![constr(0, vec![PlutusData::BoundedBytes(h.to_vec().into())])],
+        ),
+        Voter::ConstitutionalCommitteeHotScriptHash(h) => constr(
+            0,
+            vec![constr(1, vec![PlutusData::BoundedBytes(h.to_vec().into())])],
+        ),
+        Voter::DRepKeyHash(h) => constr(
+            1,
+            vec![constr(0, vec![PlutusData::BoundedBytes(h.to_vec().into())])],
+        ),
+        Voter::DRepScriptHash(h) => constr(
+            1,
+            vec![constr(1, vec![PlutusData::BoundedBytes(h.to_vec().into())])],
+        ),
+        Voter::StakePoolKeyHash(h) => constr(2, vec![PlutusData::BoundedBytes(h.to_vec().into())]),
+    }
+}
+
+/// Builds a Plutus data constructor, picking the compact tag range the CBOR
+/// encoding uses for the first 128 constructor indexes and falling back to the
+/// general `(102, [index, ...fields])` form beyond that, mirroring the
+/// `Constr`/`any_constructor` split the ledger itself uses.
+fn constr(index: u64, fields: Vec<PlutusData>) -> PlutusData {
+    let (tag, any_constructor) = if index < 7 {
+        (121 + index, None)
+    } else if index < 128 {
+        (1280 + (index - 7), None)
+    } else {
+        (102, Some(index))
+    };
+
+    PlutusData::Constr(Constr {
+        tag,
+        any_constructor,
+        fields: MaybeIndefArray::Indef(fields),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constr_uses_the_compact_tag_range_below_seven() {
+        let data = constr(3, vec![]);
+
+        assert_eq!(
+            data,
+            PlutusData::Constr(Constr {
+                tag: 124,
+                any_constructor: None,
+                fields: MaybeIndefArray::Indef(vec![]),
+            })
+        );
+    }
+
+    #[test]
+    fn constr_uses_the_extended_tag_range_up_to_127() {
+        let data = constr(10, vec![]);
+
+        assert_eq!(
+            data,
+            PlutusData::Constr(Constr {
+                tag: 1283,
+                any_constructor: None,
+                fields: MaybeIndefArray::Indef(vec![]),
+            })
+        );
+    }
+
+    #[test]
+    fn constr_falls_back_to_any_constructor_past_127() {
+        let data = constr(200, vec![]);
+
+        assert_eq!(
+            data,
+            PlutusData::Constr(Constr {
+                tag: 102,
+                any_constructor: Some(200),
+                fields: MaybeIndefArray::Indef(vec![]),
+            })
+        );
+    }
+}